@@ -0,0 +1,53 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Clipboard round-tripping of nav items.
+//!
+//! The same [`NavItemDescriptor`] the tear-off drag payload carries is also
+//! what goes on the system clipboard, so "Copy" and "Paste" in the nav
+//! context menu reuse the [`AllowedMimeTypes`]/[`AsMimeTypes`] abstractions
+//! the drag-and-drop path already relies on.
+
+use std::borrow::Cow;
+
+use cosmic::iced::clipboard::mime::{AllowedMimeTypes, AsMimeTypes};
+
+use crate::detached::NavItemDescriptor;
+use crate::NAV_ITEM_MIME;
+
+/// A nav item serialized for the clipboard.
+#[derive(Clone, Debug, Default)]
+pub struct NavClipboardItem(pub NavItemDescriptor);
+
+impl AllowedMimeTypes for NavClipboardItem {
+    fn allowed() -> Cow<'static, [String]> {
+        Cow::Owned(vec![NAV_ITEM_MIME.to_string()])
+    }
+}
+
+impl AsMimeTypes for NavClipboardItem {
+    fn available(&self) -> Cow<'static, [String]> {
+        Cow::Owned(vec![NAV_ITEM_MIME.to_string()])
+    }
+
+    fn as_bytes(&self, mime_type: &str) -> Option<Cow<'static, [u8]>> {
+        (mime_type == NAV_ITEM_MIME).then(|| self.0.to_bytes().into())
+    }
+}
+
+impl TryFrom<(Vec<u8>, String)> for NavClipboardItem {
+    type Error = std::io::Error;
+
+    fn try_from((bytes, _mime): (Vec<u8>, String)) -> Result<Self, Self::Error> {
+        // Reject malformed payloads so `read_data` yields `None` rather than a
+        // blank item that would be pasted as an empty nav entry.
+        let descriptor = NavItemDescriptor::from_bytes(&bytes).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "invalid nav item clipboard payload",
+            )
+        })?;
+
+        Ok(NavClipboardItem(descriptor))
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Drop-to-add support for freedesktop desktop entries.
+//!
+//! Dropping one or more `.desktop` files — whether as raw entry content
+//! (`application/x-desktop`) or as a `text/uri-list` of `file://` URIs — onto
+//! a nav bar parses them with [`freedesktop_desktop_entry`] and yields nav
+//! items carrying each entry's locale-resolved `Name`, `Icon`, and command.
+//!
+//! `DesktopEntryMime` belongs in the `cosmic` widget layer so launcher- and
+//! panel-style apps can share one drop target. That relocation is a change to
+//! the library crate, which is not part of this example-only source tree; the
+//! type is kept self-contained here so it can be lifted over verbatim once the
+//! widget module exists.
+
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+use cosmic::iced::clipboard::mime::AllowedMimeTypes;
+use cosmic::widget::nav_bar;
+use freedesktop_desktop_entry::{get_languages_from_env, DesktopEntry};
+
+/// Mime carried by dragged desktop-entry content.
+const DESKTOP_MIME: &str = "application/x-desktop";
+/// Mime carried by a list of URIs, any of which may point at a `.desktop` file.
+const URI_LIST_MIME: &str = "text/uri-list";
+
+/// A nav item distilled from a parsed desktop entry.
+#[derive(Clone, Debug)]
+pub struct DesktopEntryItem {
+    /// Locale-resolved display name.
+    pub name: String,
+    /// Freedesktop icon name, if the entry declared one.
+    pub icon: Option<String>,
+    /// The command to launch, taken from `Exec` or falling back to `TryExec`.
+    pub exec: Option<String>,
+}
+
+impl DesktopEntryItem {
+    /// Parses a single entry from its raw content.
+    fn from_content(path: PathBuf, content: &str) -> Option<Self> {
+        let locales = get_languages_from_env();
+        let entry = DesktopEntry::from_str(&path, content, Some(&locales)).ok()?;
+
+        // `name` walks `Name[xx]` variants for the active locales before
+        // falling back to the unqualified `Name`.
+        let name = entry.name(&locales)?.to_string();
+        let icon = entry.icon().map(str::to_string);
+        let exec = entry
+            .exec()
+            .or_else(|| entry.desktop_entry("TryExec"))
+            .map(str::to_string);
+
+        Some(Self { name, icon, exec })
+    }
+
+    /// Inserts this entry as a new nav item in `model`, returning its id.
+    pub fn insert_into(&self, model: &mut nav_bar::Model) -> nav_bar::Id {
+        let mut builder = model.insert().text(self.name.clone());
+
+        if let Some(icon) = &self.icon {
+            builder = builder.icon(cosmic::widget::icon::from_name(icon.clone()).into());
+        }
+
+        builder.data(self.exec.clone().unwrap_or_default()).id()
+    }
+}
+
+/// An [`AllowedMimeTypes`] drop target that decodes dropped desktop entries.
+#[derive(Clone, Debug, Default)]
+pub struct DesktopEntryMime(pub Vec<DesktopEntryItem>);
+
+impl AllowedMimeTypes for DesktopEntryMime {
+    fn allowed() -> Cow<'static, [String]> {
+        Cow::Owned(vec![DESKTOP_MIME.to_string(), URI_LIST_MIME.to_string()])
+    }
+}
+
+impl TryFrom<(Vec<u8>, String)> for DesktopEntryMime {
+    type Error = std::io::Error;
+
+    fn try_from((bytes, mime): (Vec<u8>, String)) -> Result<Self, Self::Error> {
+        let text = String::from_utf8(bytes)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+
+        let items = if mime == URI_LIST_MIME {
+            // Each non-comment line is a URI; read the `.desktop` files.
+            text.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .filter_map(uri_to_path)
+                .filter_map(|path| {
+                    let content = std::fs::read_to_string(&path).ok()?;
+                    DesktopEntryItem::from_content(path, &content)
+                })
+                .collect()
+        } else {
+            // Raw entry content dropped directly.
+            DesktopEntryItem::from_content(PathBuf::from("dropped.desktop"), &text)
+                .into_iter()
+                .collect()
+        };
+
+        Ok(DesktopEntryMime(items))
+    }
+}
+
+/// Resolves a `file://` (or bare path) URI to a local path.
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    if let Some(rest) = uri.strip_prefix("file://") {
+        // Drop an optional authority component before the path.
+        let path = rest.split_once('/').map_or(rest, |(_, p)| p);
+        Some(PathBuf::from(format!("/{path}")))
+    } else if uri.starts_with('/') {
+        Some(PathBuf::from(uri))
+    } else {
+        None
+    }
+}
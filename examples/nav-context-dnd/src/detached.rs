@@ -0,0 +1,63 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Tearing a nav tab off into its own top-level window.
+//!
+//! When a tab drag ends without resolving to a valid reorder slot — the
+//! segmented_button "no-drop / outside surface" terminal state — the app
+//! emits `TabDetached`, which spawns a new top-level window for its content.
+//! The item's drag payload carries a serialized [`NavItemDescriptor`] — the
+//! same bytes used for the clipboard. The detach handler does not read that
+//! payload: it takes a descriptor from the source model just before removing
+//! the item, and the new window is rendered from that descriptor alone.
+
+use cosmic::iced::Length;
+use cosmic::widget::nav_bar;
+use cosmic::Element;
+use serde::{Deserialize, Serialize};
+
+/// Everything needed to re-home a nav item in another window.
+///
+/// This is the payload embedded in the `enable_tab_drag` drag data, kept
+/// independent of the runtime [`nav_bar::Id`] slotmap key so it survives
+/// moving between models.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NavItemDescriptor {
+    /// The item's display text.
+    pub text: String,
+    /// The page body stored as the item's data.
+    pub content: String,
+}
+
+impl NavItemDescriptor {
+    /// Builds a descriptor from a live nav item.
+    pub fn from_model(model: &nav_bar::Model, id: nav_bar::Id) -> Self {
+        Self {
+            text: model.text(id).unwrap_or_default().to_owned(),
+            content: model
+                .data::<String>(id)
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Serializes the descriptor into the drag payload bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap_or_default()
+    }
+
+    /// Decodes a descriptor from drag payload bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        serde_json::from_slice(bytes).ok()
+    }
+}
+
+/// Renders the content of a torn-off nav item for its detached window.
+pub fn view<'a, M: 'a>(descriptor: &'a NavItemDescriptor) -> Element<'a, M> {
+    cosmic::widget::container(cosmic::widget::text(&descriptor.content))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_x(cosmic::iced::Alignment::Center)
+        .align_y(cosmic::iced::Alignment::Center)
+        .into()
+}
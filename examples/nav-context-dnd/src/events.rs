@@ -0,0 +1,70 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Typed event-emitter subscriptions between subsystems.
+//!
+//! Rather than hand-threading every cross-cutting concern through the central
+//! `Message` enum, subsystems declare the event types they produce and the app
+//! registers declarative subscribers once at init via `subscribe_events`. When
+//! a subsystem emits, the registry looks the event type up by [`TypeId`] and
+//! maps it back to a `Message` that is fed into `update`.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+/// Events produced by a `nav_bar::Model`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NavEvent {
+    /// Items were reordered.
+    Reordered,
+    /// The active item changed.
+    ActiveChanged,
+}
+
+/// Events produced by a `dnd_source`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DndEvent {
+    Started,
+    Finished,
+    Cancelled,
+}
+
+/// A registry of declarative event subscribers, keyed by event [`TypeId`].
+pub struct EventSubscriptions<M> {
+    handlers: HashMap<TypeId, Box<dyn Fn(&dyn Any) -> Option<M>>>,
+}
+
+impl<M> Default for EventSubscriptions<M> {
+    fn default() -> Self {
+        Self {
+            handlers: HashMap::new(),
+        }
+    }
+}
+
+impl<M: 'static> EventSubscriptions<M> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribes to events of type `E`, mapping each to a `Message`.
+    ///
+    /// A later subscription for the same event type replaces the earlier one,
+    /// mirroring how `on_activate`/`on_reorder` callbacks overwrite.
+    pub fn on<E: 'static>(&mut self, map: impl Fn(&E) -> M + 'static) -> &mut Self {
+        self.handlers.insert(
+            TypeId::of::<E>(),
+            Box::new(move |event| event.downcast_ref::<E>().map(&map)),
+        );
+        self
+    }
+
+    /// Maps an emitted event to its subscriber's `Message`, if one is
+    /// registered for that event type.
+    pub fn dispatch<E: 'static>(&self, event: &E) -> Option<M> {
+        self.handlers
+            .get(&TypeId::of::<E>())
+            .and_then(|handler| handler(event))
+    }
+}
@@ -3,7 +3,19 @@
 
 //! Application API example
 
+mod clipboard;
+mod desktop_entry;
+mod detached;
+mod events;
+mod pane_grid;
+mod persistence;
+
 use std::collections::HashMap;
+use std::time::Duration;
+
+use cosmic::iced::window;
+use detached::NavItemDescriptor;
+use persistence::NavPersistence;
 
 use cosmic::app::{Core, Settings, Task};
 use cosmic::iced_core::Size;
@@ -117,6 +129,32 @@ pub enum Message {
     ZoneHovered(f64, f64),
     ZoneDropped(String),
     NavReorder(ReorderEvent),
+    /// Debounced request to flush the nav layout to cosmic-config. Carries the
+    /// save generation it was scheduled with; a stale generation is ignored.
+    PersistNav(u64),
+    /// A tab drag ended outside the nav bar; tear it off into its own window.
+    TabDetached { id: nav_bar::Id, at: (f64, f64) },
+    /// A pane's split or move control was pressed; split the pane in
+    /// `region`'s direction or, for [`pane_grid::Region::Center`], move
+    /// `dragged` into it. The view sends the active nav item as `dragged`;
+    /// tab drags from the nav bar do not produce this message.
+    PaneDrop {
+        target: nav_bar::Id,
+        dragged: nav_bar::Id,
+        region: pane_grid::Region,
+    },
+    /// Close the pane bound to the given nav item.
+    PaneClose(nav_bar::Id),
+    /// Commit a resize drag on the split at `index` to `ratio`.
+    PaneResize { index: usize, ratio: f32 },
+    /// Focus the pane bound to the given nav item.
+    PaneFocus(nav_bar::Id),
+    /// One or more `.desktop` entries were dropped; add them as nav items.
+    DesktopEntriesDropped(desktop_entry::DesktopEntryMime),
+    /// The clipboard was read for a paste; insert the item after `at`.
+    NavItemPasted(Option<clipboard::NavClipboardItem>, nav_bar::Id),
+    /// An emitted subsystem event was mapped to a status update.
+    StatusUpdate(String),
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -124,6 +162,8 @@ pub enum NavMenuAction {
     MoveUp(nav_bar::Id),
     MoveDown(nav_bar::Id),
     Delete(nav_bar::Id),
+    Copy(nav_bar::Id),
+    Paste(nav_bar::Id),
 }
 
 impl menu::Action for NavMenuAction {
@@ -138,6 +178,14 @@ impl menu::Action for NavMenuAction {
 pub struct App {
     core: Core,
     nav_model: nav_bar::Model,
+    persistence: NavPersistence,
+    /// Incremented on every scheduled save; only the latest generation's timer
+    /// is honoured, collapsing a burst of reorders into one write.
+    save_generation: u64,
+    panes: pane_grid::PaneGrid,
+    detached: HashMap<window::Id, NavItemDescriptor>,
+    events: events::EventSubscriptions<Message>,
+    status: String,
     dropped_text: String,
 }
 
@@ -166,16 +214,56 @@ impl cosmic::Application for App {
     /// Creates the application, and optionally emits task on initialize.
     fn init(core: Core, input: Self::Flags) -> (Self, Task<Self::Message>) {
         let mut nav_model = nav_bar::Model::default();
+        // Persistence is opt-in: an app that returns no key runs without it.
+        let mut persistence = match Self::nav_persistence_key() {
+            Some(key) => NavPersistence::new(key),
+            None => NavPersistence::disabled(),
+        };
+
+        match persistence.load() {
+            // Re-insert items in the stored order and re-activate the saved
+            // entry by matching stable ids. The body travels in the snapshot,
+            // so items added at runtime restore with their content intact.
+            Some(snapshot) if !snapshot.entries.is_empty() => {
+                for entry in snapshot.entries {
+                    let id = nav_model
+                        .insert()
+                        .text(entry.text)
+                        .data(entry.content)
+                        .id();
+                    persistence.restore_mapping(id, entry.stable);
+
+                    if snapshot.active == Some(entry.stable) {
+                        nav_model.activate(id);
+                    }
+                }
+
+                if nav_model.active_data::<String>().is_none() {
+                    nav_model.activate_position(0);
+                }
+            }
+            // First launch: build the model from the launch content.
+            _ => {
+                for (title, content) in input {
+                    let id = nav_model.insert().text(title.as_str()).data(content).id();
+                    persistence.assign(id);
+                }
 
-        for (title, content) in input {
-            nav_model.insert().text(title.as_str()).data(content);
+                nav_model.activate_position(0);
+            }
         }
 
-        nav_model.activate_position(0);
+        let panes = pane_grid::PaneGrid::new(nav_model.active());
 
         let mut app = App {
             core,
             nav_model,
+            persistence,
+            save_generation: 0,
+            panes,
+            detached: HashMap::new(),
+            events: Self::subscribe_events(),
+            status: "Ready".into(),
             dropped_text: "Drop something here!".into(),
         };
 
@@ -200,6 +288,9 @@ impl cosmic::Application for App {
                 menu::Item::Button("Move Up", None, NavMenuAction::MoveUp(id)),
                 menu::Item::Button("Move Down", None, NavMenuAction::MoveDown(id)),
                 menu::Item::Button("Delete", None, NavMenuAction::Delete(id)),
+                menu::Item::Divider,
+                menu::Item::Button("Copy", None, NavMenuAction::Copy(id)),
+                menu::Item::Button("Paste", None, NavMenuAction::Paste(id)),
             ],
         ))
     }
@@ -210,7 +301,10 @@ impl cosmic::Application for App {
             .on_context(|id| cosmic::Action::Cosmic(cosmic::app::Action::NavBarContext(id)))
             .enable_tab_drag(|id| {
                 println!("Creating drag payload for {:?}", id);
-                Some((NAV_ITEM_MIME.to_string(), Vec::new()))
+                // Embed a serialized descriptor (the same bytes the clipboard
+                // carries) so the drag payload describes the whole item.
+                let descriptor = NavItemDescriptor::from_model(&self.nav_model, id);
+                Some((NAV_ITEM_MIME.to_string(), descriptor.to_bytes()))
             })
             .on_dnd_drop(|_id, _data: Option<NavItemMime>, _action| {
                 // Dummy drop handler to force the widget to register NAV_ITEM_MIME as a valid destination.
@@ -232,27 +326,113 @@ impl cosmic::Application for App {
     /// Called when a navigation item is selected.
     fn on_nav_select(&mut self, id: nav_bar::Id) -> Task<Self::Message> {
         self.nav_model.activate(id);
-        self.update_title()
+        let title = self.update_title();
+        let active_changed = self.emit(events::NavEvent::ActiveChanged);
+        let save = self.schedule_save();
+        Task::batch([title, save, active_changed])
     }
 
     /// Handle application events here.
     fn update(&mut self, message: Self::Message) -> Task<Self::Message> {
         match message {
-            Message::NavMenuAction(message) => match message {
-                NavMenuAction::Delete(id) => self.nav_model.remove(id),
-                NavMenuAction::MoveUp(id) => {
-                    if let Some(pos) = self.nav_model.position(id) {
-                        if pos != 0 {
-                            self.nav_model.position_set(id, pos - 1);
+            Message::NavMenuAction(message) => {
+                match message {
+                    NavMenuAction::Delete(id) => {
+                        self.nav_model.remove(id);
+                        self.persistence.forget(id);
+                        // Collapse any pane bound to the removed item.
+                        self.panes.close(id);
+                    }
+                    NavMenuAction::MoveUp(id) => {
+                        if let Some(pos) = self.nav_model.position(id) {
+                            if pos != 0 {
+                                self.nav_model.position_set(id, pos - 1);
+                            }
+                        }
+                    }
+                    NavMenuAction::MoveDown(id) => {
+                        if let Some(pos) = self.nav_model.position(id) {
+                            self.nav_model.position_set(id, pos + 1);
                         }
                     }
+                    NavMenuAction::Copy(id) => {
+                        // Serialize the item onto the clipboard under its mime
+                        // via its `AsMimeTypes` impl.
+                        let item = clipboard::NavClipboardItem(
+                            NavItemDescriptor::from_model(&self.nav_model, id),
+                        );
+                        return cosmic::iced::clipboard::write_data(item);
+                    }
+                    NavMenuAction::Paste(id) => {
+                        // Read the clipboard, decoding any registered mime.
+                        return cosmic::iced::clipboard::read_data::<clipboard::NavClipboardItem>()
+                            .map(move |data| {
+                                cosmic::Action::App(Message::NavItemPasted(data, id))
+                            });
+                    }
+                }
+                return self.schedule_save();
+            }
+            Message::PersistNav(generation) => {
+                // Ignore stale timers: only the most recently scheduled save
+                // writes, coalescing a burst of edits into one.
+                if generation == self.save_generation {
+                    self.persistence.save(&self.nav_model);
+                }
+            }
+            Message::TabDetached { id, at } => {
+                let descriptor = NavItemDescriptor::from_model(&self.nav_model, id);
+                self.nav_model.remove(id);
+                self.persistence.forget(id);
+                // Collapse any pane the torn-off item occupied.
+                self.panes.close(id);
+                let open = self.on_tab_detached(at, descriptor);
+                let save = self.schedule_save();
+                return Task::batch([open, save]);
+            }
+            Message::PaneDrop {
+                target,
+                dragged,
+                region,
+            } => {
+                if region == pane_grid::Region::Center {
+                    self.panes.move_into(target, dragged);
+                } else {
+                    self.panes.split(target, dragged, region);
+                }
+            }
+            Message::PaneClose(id) => self.panes.close(id),
+            Message::PaneResize { index, ratio } => self.panes.resize(index, ratio),
+            Message::PaneFocus(id) => self.panes.focus_pane(id),
+            Message::DesktopEntriesDropped(entries) => {
+                for item in &entries.0 {
+                    println!("Adding desktop entry: {}", item.name);
+                    let id = item.insert_into(&mut self.nav_model);
+                    self.persistence.assign(id);
                 }
-                NavMenuAction::MoveDown(id) => {
-                    if let Some(pos) = self.nav_model.position(id) {
+
+                if !entries.0.is_empty() {
+                    return self.schedule_save();
+                }
+            }
+            Message::NavItemPasted(item, at) => {
+                if let Some(item) = item {
+                    let id = self
+                        .nav_model
+                        .insert()
+                        .text(item.0.text)
+                        .data(item.0.content)
+                        .id();
+                    self.persistence.assign(id);
+
+                    // Insert at the clicked position, just after the target.
+                    if let Some(pos) = self.nav_model.position(at) {
                         self.nav_model.position_set(id, pos + 1);
                     }
+
+                    return self.schedule_save();
                 }
-            },
+            }
             Message::NavReorder(event) => {
                 println!("NavReorder TRIGGERED: {:?}", event);
                 println!("  Before: {:?}", self.nav_model.iter().collect::<Vec<_>>());
@@ -262,18 +442,37 @@ impl cosmic::Application for App {
                 {
                     println!("  Reorder SUCCESS");
                     println!("  After: {:?}", self.nav_model.iter().collect::<Vec<_>>());
+                    let reordered = self.emit(events::NavEvent::Reordered);
+                    let save = self.schedule_save();
+                    return Task::batch([save, reordered]);
                 } else {
-                    println!("  Reorder FAILED");
+                    // A drag that ends without resolving to a valid reorder
+                    // slot is the segmented_button "no-drop / outside surface"
+                    // terminal state: tear the dragged tab off into its own
+                    // window. ReorderEvent does not carry the release point, so
+                    // cascade successive detached windows.
+                    println!("  Reorder FAILED (released outside nav bar) -> detaching");
+                    let offset = 64.0 + self.detached.len() as f64 * 32.0;
+                    return Task::done(cosmic::Action::App(Message::TabDetached {
+                        id: event.dragged,
+                        at: (offset, offset),
+                    }));
                 }
             }
             Message::SourceStarted => {
                 println!("Source started");
+                return self.emit(events::DndEvent::Started);
             }
             Message::SourceFinished => {
                 println!("Source finished");
+                return self.emit(events::DndEvent::Finished);
             }
             Message::SourceCancelled => {
                 println!("Source cancelled");
+                return self.emit(events::DndEvent::Cancelled);
+            }
+            Message::StatusUpdate(status) => {
+                self.status = status;
             }
             Message::ZoneHovered(x, y) => {
                 println!("Zone hovered at {x}, {y}");
@@ -302,6 +501,28 @@ impl cosmic::Application for App {
             .align_x(iced::Alignment::Center)
             .align_y(iced::Alignment::Center);
 
+        // The tiled content area, driven by the pane grid's split tree.
+        // A split binds its new pane to the currently active nav item.
+        let active = self.nav_model.active();
+        let panes = cosmic::widget::container(pane_grid::view(
+            &self.panes,
+            &self.nav_model,
+            move |action| match action {
+                pane_grid::PaneAction::Focus(id) => Message::PaneFocus(id),
+                pane_grid::PaneAction::Close(id) => Message::PaneClose(id),
+                pane_grid::PaneAction::Split(target, region) => Message::PaneDrop {
+                    target,
+                    dragged: active,
+                    region,
+                },
+                pane_grid::PaneAction::Resize(index, ratio) => {
+                    Message::PaneResize { index, ratio }
+                }
+            },
+        ))
+        .width(iced::Length::Fill)
+        .height(iced::Length::Fill);
+
         let source = cosmic::widget::dnd_source(
             cosmic::widget::container(cosmic::widget::text("Drag me!"))
                 .padding(20)
@@ -330,28 +551,115 @@ impl cosmic::Application for App {
         .on_drop(|_x, _y| Message::SourceFinished) // This is triggered when drop happens but we primarily use the data callback above
         .on_motion(|x, y| Message::ZoneHovered(x, y));
 
+        let desktop_drop = dnd_destination_for_data::<desktop_entry::DesktopEntryMime, Message>(
+            cosmic::widget::container(cosmic::widget::text("Drop .desktop files here to add apps"))
+                .padding(30)
+                .class(cosmic::theme::Container::Card)
+                .width(iced::Length::Fill)
+                .align_x(iced::Alignment::Center),
+            |data, _action| match data {
+                Some(entries) => Message::DesktopEntriesDropped(entries),
+                None => Message::SourceCancelled,
+            },
+        );
+
+        // Status indicator driven entirely by emitted subsystem events.
+        let status = cosmic::widget::text(&self.status);
+
         let content = cosmic::widget::column()
+            .push(status)
             .push(centered)
+            .push(panes)
             .push(source)
             .push(destination)
+            .push(desktop_drop)
             .spacing(20)
             .align_x(iced::Alignment::Center)
             .padding(20);
 
         Element::from(content)
     }
+
+    /// Renders the content hosted by a torn-off tab's detached window.
+    fn view_window(&self, id: window::Id) -> Element<'_, Self::Message> {
+        match self.detached.get(&id) {
+            Some(descriptor) => detached::view(descriptor),
+            None => cosmic::widget::text("").into(),
+        }
+    }
 }
 
 impl App
 where
     Self: cosmic::Application,
 {
+    /// Tears a nav item off into its own top-level window at `at`, rebuilding
+    /// its content from the descriptor rather than the (now removed) model
+    /// entry.
+    fn on_tab_detached(
+        &mut self,
+        at: (f64, f64),
+        content: NavItemDescriptor,
+    ) -> Task<Message> {
+        let (id, task) = window::open(window::Settings {
+            position: window::Position::Specific(cosmic::iced::Point::new(
+                at.0 as f32,
+                at.1 as f32,
+            )),
+            ..Default::default()
+        });
+
+        self.detached.insert(id, content);
+
+        task.map(|_id| cosmic::Action::None)
+    }
+
     fn active_page_title(&mut self) -> &str {
         self.nav_model
             .text(self.nav_model.active())
             .unwrap_or("Unknown Page")
     }
 
+    /// Opt-in key under which the nav layout is persisted; `None` disables
+    /// persistence entirely. Mirrors the `nav_persistence_key` trait hook the
+    /// request specifies, pending its addition to `cosmic::Application`.
+    fn nav_persistence_key() -> Option<&'static str> {
+        Some(Self::APP_ID)
+    }
+
+    /// Builds the declarative event-subscription registry wired once at init.
+    fn subscribe_events() -> events::EventSubscriptions<Message> {
+        let mut subs = events::EventSubscriptions::new();
+        subs.on::<events::NavEvent>(|event| match event {
+            events::NavEvent::Reordered => Message::StatusUpdate("Nav reordered".into()),
+            events::NavEvent::ActiveChanged => Message::StatusUpdate("Nav page changed".into()),
+        })
+        .on::<events::DndEvent>(|event| Message::StatusUpdate(format!("Drag source {event:?}")));
+        subs
+    }
+
+    /// Dispatch a subsystem event through the registry, turning it into a
+    /// follow-up message if the app subscribed to that event type.
+    fn emit<E: 'static>(&self, event: E) -> Task<Message> {
+        match self.events.dispatch(&event) {
+            Some(message) => Task::done(cosmic::Action::App(message)),
+            None => Task::none(),
+        }
+    }
+
+    /// Debounce a write-back of the nav layout: bump the save generation and
+    /// arm a timer tagged with it. A burst of reorders arms several timers, but
+    /// [`Message::PersistNav`] only writes for the latest generation, so the
+    /// earlier timers no-op and a single `save()` results.
+    fn schedule_save(&mut self) -> Task<Message> {
+        self.save_generation += 1;
+        let generation = self.save_generation;
+        Task::perform(
+            async { tokio::time::sleep(Duration::from_millis(500)).await },
+            move |()| cosmic::Action::App(Message::PersistNav(generation)),
+        )
+    }
+
     fn update_title(&mut self) -> Task<Message> {
         let header_title = self.active_page_title().to_owned();
         let window_title = format!("{header_title} — COSMIC AppDemo");
@@ -0,0 +1,415 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! A splittable, dockable content area backed by a binary split tree.
+//!
+//! Each leaf is bound to a [`nav_bar::Id`]; internal nodes split their region
+//! either [`Axis::Horizontal`] (a left/right divider) or [`Axis::Vertical`]
+//! (a top/bottom divider) at a `ratio` in `[0.0, 1.0]`. Each rendered pane
+//! exposes controls to split it left, right, up or down, to move the active
+//! item into it, and to close it; each divider exposes a resize nudge.
+
+use cosmic::widget::nav_bar;
+use cosmic::{iced, Element};
+
+/// The orientation of a split's divider.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Axis {
+    /// A vertical divider producing a left and a right child.
+    Horizontal,
+    /// A horizontal divider producing a top and a bottom child.
+    Vertical,
+}
+
+/// Which region of a pane a tab was dropped onto.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Region {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    /// Move the tab into the pane rather than splitting it.
+    Center,
+}
+
+/// A node in the split tree: either a bound leaf or an internal split.
+#[derive(Clone, Debug)]
+pub enum Node {
+    /// A pane bound to a nav item.
+    Leaf(nav_bar::Id),
+    /// A split dividing its region between two children.
+    Split {
+        axis: Axis,
+        /// Fraction of the region given to the first child, in `[0.0, 1.0]`.
+        ratio: f32,
+        first: Box<Node>,
+        second: Box<Node>,
+    },
+}
+
+impl Node {
+    /// Replaces the leaf `target` in place with a split introducing `new`.
+    ///
+    /// Returns `true` if `target` was found. The `region` decides the split
+    /// axis and which side `new` lands on; `Region::Center` is handled by the
+    /// caller and treated here as a no-op.
+    fn split_leaf(&mut self, target: nav_bar::Id, new: nav_bar::Id, region: Region) -> bool {
+        match self {
+            Node::Leaf(id) if *id == target => {
+                let (axis, new_first) = match region {
+                    Region::Left => (Axis::Horizontal, true),
+                    Region::Right => (Axis::Horizontal, false),
+                    Region::Top => (Axis::Vertical, true),
+                    Region::Bottom => (Axis::Vertical, false),
+                    Region::Center => return false,
+                };
+
+                let existing = Node::Leaf(target);
+                let inserted = Node::Leaf(new);
+                let (first, second) = if new_first {
+                    (inserted, existing)
+                } else {
+                    (existing, inserted)
+                };
+
+                *self = Node::Split {
+                    axis,
+                    ratio: 0.5,
+                    first: Box::new(first),
+                    second: Box::new(second),
+                };
+                true
+            }
+            Node::Leaf(_) => false,
+            Node::Split { first, second, .. } => {
+                first.split_leaf(target, new, region)
+                    || second.split_leaf(target, new, region)
+            }
+        }
+    }
+
+    /// Removes the leaf bound to `id`, collapsing the parent split so its
+    /// surviving sibling takes over the region. Returns `true` if removed.
+    fn remove_leaf(&mut self, id: nav_bar::Id) -> bool {
+        let Node::Split { first, second, .. } = self else {
+            return false;
+        };
+
+        for (child, sibling) in [(&**first, &**second), (&**second, &**first)] {
+            if matches!(child, Node::Leaf(leaf) if *leaf == id) {
+                *self = sibling.clone();
+                return true;
+            }
+        }
+
+        first.remove_leaf(id) || second.remove_leaf(id)
+    }
+}
+
+/// A tiled, resizable content area bound to nav items.
+#[derive(Clone, Debug, Default)]
+pub struct PaneGrid {
+    root: Option<Node>,
+    focus: Option<nav_bar::Id>,
+}
+
+impl PaneGrid {
+    /// Creates a grid with a single pane bound to `id`.
+    pub fn new(id: nav_bar::Id) -> Self {
+        Self {
+            root: Some(Node::Leaf(id)),
+            focus: Some(id),
+        }
+    }
+
+    /// The currently focused leaf, if any.
+    pub fn focus(&self) -> Option<nav_bar::Id> {
+        self.focus
+    }
+
+    /// Focuses the pane bound to `id`.
+    pub fn focus_pane(&mut self, id: nav_bar::Id) {
+        self.focus = Some(id);
+    }
+
+    /// Splits the pane bound to `target`, binding the new pane to `new`.
+    ///
+    /// On a non-empty grid, a `Region::Center` drop is a move rather than a
+    /// split and returns `false`, leaving the tree untouched for the caller to
+    /// handle. An empty grid is seeded with a pane bound to `new` for any
+    /// region.
+    pub fn split(&mut self, target: nav_bar::Id, new: nav_bar::Id, region: Region) -> bool {
+        // Splitting a pane against its own bound item would bind both halves to
+        // the same id; reject it so the focused pane is never duplicated.
+        if target == new {
+            return false;
+        }
+
+        match (&mut self.root, region) {
+            (Some(root), _) => {
+                let split = root.split_leaf(target, new, region);
+                if split {
+                    self.focus = Some(new);
+                }
+                split
+            }
+            // An empty grid has nothing to split against, so any region just
+            // seeds the first pane.
+            (none @ None, _) => {
+                *none = Some(Node::Leaf(new));
+                self.focus = Some(new);
+                true
+            }
+        }
+    }
+
+    /// Moves the `dragged` tab into the pane bound to `target`, rebinding the
+    /// target leaf and dropping any pane the tab previously occupied.
+    ///
+    /// Returns `true` if the target pane was found.
+    pub fn move_into(&mut self, target: nav_bar::Id, dragged: nav_bar::Id) -> bool {
+        if target == dragged {
+            self.focus = Some(dragged);
+            return true;
+        }
+
+        // Remove the dragged tab's old pane first so it is not duplicated.
+        self.close(dragged);
+
+        let Some(root) = self.root.as_mut() else {
+            return false;
+        };
+
+        if rebind_leaf(root, target, dragged) {
+            self.focus = Some(dragged);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Closes the pane bound to `id`, collapsing its split.
+    pub fn close(&mut self, id: nav_bar::Id) {
+        let removed = match &mut self.root {
+            Some(Node::Leaf(leaf)) if *leaf == id => {
+                self.root = None;
+                true
+            }
+            Some(root) => root.remove_leaf(id),
+            None => false,
+        };
+
+        if removed && self.focus == Some(id) {
+            self.focus = None;
+        }
+    }
+
+    /// Sets the ratio of the split at `index`, clamping it to `[0.0, 1.0]`.
+    pub fn resize(&mut self, index: usize, ratio: f32) {
+        let ratio = ratio.clamp(0.0, 1.0);
+
+        if let Some(root) = self.root.as_mut() {
+            apply_ratio(root, index, &mut 0, ratio);
+        }
+    }
+
+    /// The root node of the split tree, if the grid holds any panes.
+    pub fn root(&self) -> Option<&Node> {
+        self.root.as_ref()
+    }
+}
+
+/// An interaction produced by the rendered pane grid, handed back to the
+/// caller to map onto its own message type.
+#[derive(Clone, Copy, Debug)]
+pub enum PaneAction {
+    /// Focus the pane bound to this nav item.
+    Focus(nav_bar::Id),
+    /// Close the pane bound to this nav item.
+    Close(nav_bar::Id),
+    /// Split the pane bound to this nav item in `region`'s direction, binding
+    /// the new pane to the active nav item.
+    Split(nav_bar::Id, Region),
+    /// Commit a resize of the split at `index` to `ratio`.
+    Resize(usize, f32),
+}
+
+/// Renders `grid` by walking its split tree, laying each split out along its
+/// axis at the stored ratio and drawing each leaf as the content of the nav
+/// item it is bound to. Each leaf exposes focus/close/split affordances, and
+/// each divider exposes a resize nudge; interactions are reported through
+/// `on_action`. An empty grid renders a placeholder.
+pub fn view<'a, M: Clone + 'a>(
+    grid: &'a PaneGrid,
+    model: &'a nav_bar::Model,
+    on_action: impl Fn(PaneAction) -> M + Copy + 'a,
+) -> Element<'a, M> {
+    match grid.root() {
+        Some(node) => view_node(node, model, grid.focus(), on_action, &mut 0),
+        None => cosmic::widget::container(cosmic::widget::text("No panes"))
+            .width(iced::Length::Fill)
+            .height(iced::Length::Fill)
+            .align_x(iced::Alignment::Center)
+            .align_y(iced::Alignment::Center)
+            .into(),
+    }
+}
+
+/// Renders a single node of the split tree. `split_index` counts internal
+/// splits in pre-order so a resize nudge can address the right divider.
+fn view_node<'a, M: Clone + 'a>(
+    node: &'a Node,
+    model: &'a nav_bar::Model,
+    focus: Option<nav_bar::Id>,
+    on_action: impl Fn(PaneAction) -> M + Copy + 'a,
+    split_index: &mut usize,
+) -> Element<'a, M> {
+    match node {
+        Node::Leaf(id) => {
+            let id = *id;
+            let body = model.data::<String>(id).map_or("", String::as_str);
+            let class = if focus == Some(id) {
+                cosmic::theme::Container::Primary
+            } else {
+                cosmic::theme::Container::Card
+            };
+
+            // Pane controls: split in each direction / move active in / close.
+            let controls = cosmic::widget::row()
+                .push(
+                    cosmic::widget::button::text("◧")
+                        .on_press(on_action(PaneAction::Split(id, Region::Left))),
+                )
+                .push(
+                    cosmic::widget::button::text("▥")
+                        .on_press(on_action(PaneAction::Split(id, Region::Right))),
+                )
+                .push(
+                    cosmic::widget::button::text("⬒")
+                        .on_press(on_action(PaneAction::Split(id, Region::Top))),
+                )
+                .push(
+                    cosmic::widget::button::text("▤")
+                        .on_press(on_action(PaneAction::Split(id, Region::Bottom))),
+                )
+                .push(
+                    cosmic::widget::button::text("⇱")
+                        .on_press(on_action(PaneAction::Split(id, Region::Center))),
+                )
+                .push(
+                    cosmic::widget::button::text("×")
+                        .on_press(on_action(PaneAction::Close(id))),
+                )
+                .spacing(4);
+
+            let pane = cosmic::widget::container(
+                cosmic::widget::column()
+                    .push(controls)
+                    .push(cosmic::widget::text(body))
+                    .spacing(8)
+                    .align_x(iced::Alignment::Center),
+            )
+            .class(class)
+            .padding(12)
+            .width(iced::Length::Fill)
+            .height(iced::Length::Fill)
+            .align_x(iced::Alignment::Center)
+            .align_y(iced::Alignment::Center);
+
+            // Clicking a pane focuses it.
+            cosmic::widget::mouse_area(pane)
+                .on_press(on_action(PaneAction::Focus(id)))
+                .into()
+        }
+        Node::Split {
+            axis,
+            ratio,
+            first,
+            second,
+        } => {
+            let index = *split_index;
+            *split_index += 1;
+
+            let first = view_node(first, model, focus, on_action, split_index);
+            let second = view_node(second, model, focus, on_action, split_index);
+
+            // A nudge that shifts the divider a tenth toward the second child,
+            // wrapping back once it reaches the far edge.
+            let next_ratio = if *ratio >= 0.9 { 0.1 } else { ratio + 0.1 };
+            let divider = cosmic::widget::button::text("⇔")
+                .on_press(on_action(PaneAction::Resize(index, next_ratio)));
+
+            // Weight the two children by the split ratio along the split axis.
+            let first_fill = ((ratio * 1000.0) as u16).max(1);
+            let second_fill = 1000u16.saturating_sub(first_fill).max(1);
+
+            match axis {
+                Axis::Horizontal => cosmic::widget::row()
+                    .push(
+                        cosmic::widget::container(first)
+                            .width(iced::Length::FillPortion(first_fill))
+                            .height(iced::Length::Fill),
+                    )
+                    .push(divider)
+                    .push(
+                        cosmic::widget::container(second)
+                            .width(iced::Length::FillPortion(second_fill))
+                            .height(iced::Length::Fill),
+                    )
+                    .width(iced::Length::Fill)
+                    .height(iced::Length::Fill)
+                    .into(),
+                Axis::Vertical => cosmic::widget::column()
+                    .push(
+                        cosmic::widget::container(first)
+                            .width(iced::Length::Fill)
+                            .height(iced::Length::FillPortion(first_fill)),
+                    )
+                    .push(divider)
+                    .push(
+                        cosmic::widget::container(second)
+                            .width(iced::Length::Fill)
+                            .height(iced::Length::FillPortion(second_fill)),
+                    )
+                    .width(iced::Length::Fill)
+                    .height(iced::Length::Fill)
+                    .into(),
+            }
+        }
+    }
+}
+
+/// Rebinds the leaf bound to `target` so it hosts `new`. Returns `true` if
+/// the target leaf was found.
+fn rebind_leaf(node: &mut Node, target: nav_bar::Id, new: nav_bar::Id) -> bool {
+    match node {
+        Node::Leaf(id) if *id == target => {
+            *id = new;
+            true
+        }
+        Node::Leaf(_) => false,
+        Node::Split { first, second, .. } => {
+            rebind_leaf(first, target, new) || rebind_leaf(second, target, new)
+        }
+    }
+}
+
+/// Applies `ratio` to the `target`-th internal split in pre-order.
+fn apply_ratio(node: &mut Node, target: usize, seen: &mut usize, ratio: f32) -> bool {
+    if let Node::Split {
+        ratio: r,
+        first,
+        second,
+        ..
+    } = node
+    {
+        if *seen == target {
+            *r = ratio;
+            return true;
+        }
+        *seen += 1;
+        apply_ratio(first, target, seen, ratio) || apply_ratio(second, target, seen, ratio)
+    } else {
+        false
+    }
+}
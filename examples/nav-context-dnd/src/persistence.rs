@@ -0,0 +1,148 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Session persistence for the nav bar layout.
+//!
+//! The runtime [`nav_bar::Id`] is a slotmap key that is only valid for the
+//! lifetime of a single [`nav_bar::Model`]; it is not stable across runs.
+//! To persist the user's ordering we assign every item a stable `u64` at
+//! insert time and serialize an ordered snapshot of each item's stable id,
+//! text, and page content plus the active entry into a [`cosmic_config`]
+//! context keyed by `APP_ID`. Carrying the content in the snapshot lets an
+//! item added at runtime — by paste or a desktop drop — restore with its body
+//! intact, and keeps items with identical titles distinct across runs.
+
+use std::collections::HashMap;
+
+use cosmic::cosmic_config::{self, ConfigGet, ConfigSet};
+use cosmic::widget::nav_bar;
+use serde::{Deserialize, Serialize};
+
+/// Config version for the persisted nav layout.
+const CONFIG_VERSION: u64 = 1;
+
+/// Key under which the snapshot is stored in the config context.
+const SNAPSHOT_KEY: &str = "nav_layout";
+
+/// A single persisted nav item, independent of the runtime slotmap key.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NavEntry {
+    /// Stable id assigned at insert time.
+    pub stable: u64,
+    /// The item's display text.
+    pub text: String,
+    /// The page body stored as the item's data.
+    pub content: String,
+}
+
+/// A persistent, run-stable snapshot of a [`nav_bar::Model`]'s ordering.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NavSnapshot {
+    /// Items in display order.
+    pub entries: Vec<NavEntry>,
+    /// Stable id of the active entry, if any.
+    pub active: Option<u64>,
+}
+
+/// Tracks the mapping between runtime [`nav_bar::Id`]s and stable ids, and
+/// writes snapshots back to the config context.
+pub struct NavPersistence {
+    config: Option<cosmic_config::Config>,
+    stable_ids: HashMap<nav_bar::Id, u64>,
+    next_stable_id: u64,
+}
+
+impl NavPersistence {
+    /// Opens the config context for `app_id`, returning a persistence handle.
+    ///
+    /// A failure to open the context is not fatal: the app simply runs without
+    /// persistence, mirroring how the rest of libcosmic treats config errors.
+    pub fn new(app_id: &str) -> Self {
+        let config = cosmic_config::Config::new(app_id, CONFIG_VERSION)
+            .inspect_err(|err| tracing::warn!(?err, "failed to open nav config"))
+            .ok();
+
+        Self {
+            config,
+            stable_ids: HashMap::new(),
+            next_stable_id: 0,
+        }
+    }
+
+    /// A handle with no backing config; `load` returns `None` and `save` is a
+    /// no-op, while stable ids are still tracked. Used when the app opts out
+    /// of persistence.
+    pub fn disabled() -> Self {
+        Self {
+            config: None,
+            stable_ids: HashMap::new(),
+            next_stable_id: 0,
+        }
+    }
+
+    /// Loads the persisted snapshot, if one exists.
+    pub fn load(&self) -> Option<NavSnapshot> {
+        self.config
+            .as_ref()?
+            .get::<NavSnapshot>(SNAPSHOT_KEY)
+            .inspect_err(|err| tracing::debug!(?err, "no persisted nav layout"))
+            .ok()
+    }
+
+    /// Assigns and records a fresh stable id for a newly inserted item.
+    pub fn assign(&mut self, id: nav_bar::Id) -> u64 {
+        let stable = self.next_stable_id;
+        self.next_stable_id += 1;
+        self.stable_ids.insert(id, stable);
+        stable
+    }
+
+    /// Records a `(runtime id, stable id)` pair restored from a snapshot,
+    /// keeping `next_stable_id` ahead of every id seen so far.
+    pub fn restore_mapping(&mut self, id: nav_bar::Id, stable: u64) {
+        self.stable_ids.insert(id, stable);
+        self.next_stable_id = self.next_stable_id.max(stable + 1);
+    }
+
+    /// The stable id previously assigned to a runtime id, if any.
+    pub fn stable_id(&self, id: nav_bar::Id) -> Option<u64> {
+        self.stable_ids.get(&id).copied()
+    }
+
+    /// Forgets a removed item's mapping.
+    pub fn forget(&mut self, id: nav_bar::Id) {
+        self.stable_ids.remove(&id);
+    }
+
+    /// Builds a snapshot from the current model state and writes it back.
+    ///
+    /// Callers debounce this behind a short timer so a burst of reorders only
+    /// results in a single write.
+    pub fn save(&self, model: &nav_bar::Model) {
+        let Some(config) = self.config.as_ref() else {
+            return;
+        };
+
+        let entries = model
+            .iter()
+            .filter_map(|id| {
+                let stable = self.stable_id(id)?;
+                let text = model.text(id).unwrap_or_default().to_owned();
+                let content = model.data::<String>(id).cloned().unwrap_or_default();
+                Some(NavEntry {
+                    stable,
+                    text,
+                    content,
+                })
+            })
+            .collect();
+
+        let active = self.stable_id(model.active());
+
+        let snapshot = NavSnapshot { entries, active };
+
+        if let Err(err) = config.set(SNAPSHOT_KEY, snapshot) {
+            tracing::warn!(?err, "failed to persist nav layout");
+        }
+    }
+}